@@ -0,0 +1,114 @@
+//! Parsing helpers for the `#[approx(...)]` struct and field attributes shared by all of the
+//! derive macros in this crate.
+
+/// How a field should be brought in line with the inferred epsilon type when its own type
+/// differs from it.
+pub enum TypeCast {
+    /// Cast the field itself (`self.field as EpsilonType`).
+    CastField,
+    /// Cast the epsilon/max_relative value to the field's own type instead. The max_ulps value
+    /// is always a `u32` and is passed through uncast.
+    CastValue,
+}
+
+/// Parsed `#[approx(...)]` attributes attached to an individual field.
+#[derive(Default)]
+pub struct FieldArgs {
+    pub skip: bool,
+    pub cast_strategy: Option<TypeCast>,
+    pub epsilon_static_value: Option<syn::Expr>,
+    pub max_relative_static_value: Option<syn::Expr>,
+    pub max_ulps_static_value: Option<syn::Expr>,
+    pub rhs_field: Option<syn::Ident>,
+    pub map: Option<syn::Path>,
+    pub compare_with: Option<syn::Path>,
+}
+
+impl FieldArgs {
+    fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut field_args = FieldArgs::default();
+        for attr in attrs {
+            if !attr.path().is_ident("approx") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    field_args.skip = true;
+                } else if meta.path.is_ident("cast_field") {
+                    field_args.cast_strategy = Some(TypeCast::CastField);
+                } else if meta.path.is_ident("cast_value") {
+                    field_args.cast_strategy = Some(TypeCast::CastValue);
+                } else if meta.path.is_ident("static_epsilon") {
+                    field_args.epsilon_static_value = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("static_max_relative") {
+                    field_args.max_relative_static_value = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("static_max_ulps") {
+                    field_args.max_ulps_static_value = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("rhs_field") {
+                    field_args.rhs_field = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("map") {
+                    field_args.map = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("compare_with") {
+                    field_args.compare_with = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unsupported approx field attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(field_args)
+    }
+}
+
+/// A single field (or enum variant field) together with its parsed `#[approx(...)]` attributes.
+pub struct FieldWithArgs {
+    pub field: syn::Field,
+    pub args: FieldArgs,
+}
+
+impl FieldWithArgs {
+    pub fn from_field(field: &syn::Field) -> syn::Result<Self> {
+        Ok(Self {
+            field: field.clone(),
+            args: FieldArgs::from_attrs(&field.attrs)?,
+        })
+    }
+}
+
+/// Parsed `#[approx(...)]` attributes attached to the struct (or enum) itself.
+#[derive(Default)]
+pub struct StructArgs {
+    pub epsilon_type: Option<syn::Type>,
+    pub default_epsilon_value: Option<syn::Expr>,
+    pub default_max_relative_value: Option<syn::Expr>,
+    pub default_max_ulps_value: Option<syn::Expr>,
+    pub rhs_type: Option<syn::Type>,
+}
+
+impl StructArgs {
+    pub fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut struct_args = StructArgs::default();
+        for attr in attrs {
+            if !attr.path().is_ident("approx") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("epsilon_type") {
+                    struct_args.epsilon_type = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("default_epsilon") {
+                    struct_args.default_epsilon_value = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("default_max_relative") {
+                    struct_args.default_max_relative_value = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("default_max_ulps") {
+                    struct_args.default_max_ulps_value = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("rhs_type") {
+                    struct_args.rhs_type = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unsupported approx struct attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(struct_args)
+    }
+}
@@ -216,49 +216,380 @@
 //! approx::assert_relative_eq!(car1, car2, max_relative = 0.05);
 //! approx::assert_relative_ne!(car1, car2, max_relative = 0.01);
 //! ```
+//!
+//! ## Ulps Comparisons
+//! Besides [AbsDiffEq] and [RelativeEq], `approx` also offers [UlpsEq], which compares floats by
+//! the number of representable values ("units in the last place") between them. Deriving
+//! [UlpsEq] also derives [AbsDiffEq] and [RelativeEq], since `approx`'s [UlpsEq] trait requires
+//! both.
+//!
+//! ```
+//! # use approx_derive::*;
+//! #[derive(UlpsEq, PartialEq, Debug)]
+//! #[approx(default_max_ulps = 5)]
+//! struct Measurement {
+//!     value: f64,
+//! }
+//!
+//! let m1 = Measurement { value: 1.0 };
+//! let m2 = Measurement { value: 1.0 + 3.0 * f64::EPSILON };
+//! approx::assert_ulps_eq!(m1, m2);
+//! ```
+//!
+//! As with [AbsDiffEq], individual fields can be pinned to a static number of max ulps using
+//! `#[approx(static_max_ulps = N)]`.
+//!
+//! # Enums
+//! The derive macros also support enums. Two values are only considered equal if they are the
+//! same variant, in which case the comparison recurses field by field; mismatched variants are
+//! never equal. All of the field and struct-level attributes above work the same way on enum
+//! variants and the enum itself.
+//!
+//! ```
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! enum Shape {
+//!     Circle { radius: f64 },
+//!     Point,
+//! }
+//!
+//! approx::assert_abs_diff_eq!(
+//!     Shape::Circle { radius: 1.0 },
+//!     Shape::Circle { radius: 1.0 + f64::EPSILON },
+//! );
+//! approx::assert_abs_diff_ne!(Shape::Circle { radius: 1.0 }, Shape::Point);
+//! ```
+//!
+//! # Tuple and Unit Structs
+//! Tuple structs are compared field by field in declaration order, addressed by index rather
+//! than name. Unit structs always compare equal.
+//!
+//! ```
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! struct Meters(f64, f64);
+//!
+//! approx::assert_abs_diff_eq!(Meters(1.0, 2.0), Meters(1.0 + f64::EPSILON, 2.0));
+//! ```
+//!
+//! ### Heterogeneous Comparisons
+//! The [AbsDiffEq] and [RelativeEq] traits are generic over the type being compared against
+//! (`AbsDiffEq<Rhs = Self>`), so we can compare two different types field by field. Use
+//! `#[approx(rhs_type = OtherType)]` on the struct to pick the other type, and
+//! `#[approx(rhs_field = other_name)]` on a field if it is named differently on `OtherType`
+//! (it defaults to the same name).
+//!
+//! `AbsDiffEq<Rhs>` has `PartialEq<Rhs>` as a supertrait. For the usual `Rhs = Self` case,
+//! `#[derive(PartialEq)]` already covers this, but for a custom `Rhs` that impl is on the user to
+//! provide, since the derive only ever implements the approx traits themselves.
+//!
+//! ```
+//! # use approx_derive::*;
+//! #[derive(Debug)]
+//! struct PolarPosition {
+//!     radius: f64,
+//!     angle: f64,
+//! }
+//!
+//! #[derive(AbsDiffEq, Debug)]
+//! #[approx(rhs_type = PolarPosition)]
+//! struct Position {
+//!     #[approx(rhs_field = radius)]
+//!     x: f64,
+//!     #[approx(rhs_field = angle)]
+//!     y: f64,
+//! }
+//!
+//! impl PartialEq<PolarPosition> for Position {
+//!     fn eq(&self, other: &PolarPosition) -> bool {
+//!         self.x == other.radius && self.y == other.angle
+//!     }
+//! }
+//!
+//! let position = Position { x: 1.0, y: 0.0 };
+//! let polar = PolarPosition { radius: 1.0, angle: 0.0 };
+//! approx::assert_abs_diff_eq!(position, polar);
+//! ```
+//!
+//! `#[derive(UlpsEq)]` always compares against `Self`, so `#[approx(rhs_type = ...)]` is rejected
+//! at compile time rather than being silently ignored.
+//! ```compile_fail
+//! # use approx_derive::*;
+//! #[derive(Debug)]
+//! struct PolarPosition {
+//!     radius: f64,
+//!     angle: f64,
+//! }
+//!
+//! #[derive(UlpsEq, Debug)]
+//! #[approx(rhs_type = PolarPosition)]
+//! struct Position {
+//!     #[approx(rhs_field = radius)]
+//!     x: f64,
+//!     #[approx(rhs_field = angle)]
+//!     y: f64,
+//! }
+//!
+//! impl PartialEq<PolarPosition> for Position {
+//!     fn eq(&self, other: &PolarPosition) -> bool {
+//!         self.x == other.radius && self.y == other.angle
+//!     }
+//! }
+//! ```
+//!
+//! ### Custom Field Comparisons
+//! Not every field implements the approx traits itself. `#[approx(map = path::to_fn)]` projects
+//! a field through a function before comparing the results, so e.g. a [String] field can be
+//! compared by length instead of being skipped entirely.
+//!
+//! Fields with `map` or `compare_with` are passed over when the `EPSILON` type is inferred; if
+//! no other field is left, it defaults to `f64`.
+//!
+//! ```
+//! # use approx_derive::*;
+//! fn len(s: &String) -> f64 {
+//!     s.len() as f64
+//! }
+//!
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! struct Label {
+//!     #[approx(map = len)]
+//!     text: String,
+//! }
+//!
+//! approx::assert_abs_diff_eq!(
+//!     Label { text: "hello".to_string() },
+//!     Label { text: "world".to_string() },
+//! );
+//! ```
+//!
+//! For cases `map` cannot express, `#[approx(compare_with = path::to_fn)]` replaces the
+//! comparison entirely with a call to a function of signature
+//! `fn(&FieldType, &FieldType, Epsilon) -> bool`. The same function is called no matter which
+//! trait is being derived, so it also has to be the final word on equality under
+//! [RelativeEq]/[UlpsEq]: `max_relative`/`max_ulps` are not passed through, since a custom
+//! comparison already fully decides equality from `epsilon` alone.
+//!
+//! ```
+//! # use approx_derive::*;
+//! fn close_enough(a: &String, b: &String, epsilon: f64) -> bool {
+//!     (a.len() as f64 - b.len() as f64).abs() <= epsilon
+//! }
+//!
+//! #[derive(RelativeEq, PartialEq, Debug)]
+//! struct Tag {
+//!     #[approx(compare_with = close_enough)]
+//!     name: String,
+//! }
+//!
+//! approx::assert_relative_eq!(
+//!     Tag { name: "hello".to_string() },
+//!     Tag { name: "world".to_string() },
+//! );
+//! ```
 
 mod args_parsing;
 use args_parsing::*;
 
-struct AbsDiffEqParser {
-    item_struct: syn::ItemStruct,
+/// An enum variant together with the parsed `#[approx(...)]` attributes of its fields.
+struct VariantWithArgs {
+    ident: syn::Ident,
+    fields: syn::Fields,
     fields_with_args: Vec<FieldWithArgs>,
+}
+
+/// The shape of the item the derive macro was applied to: either a struct with a flat field
+/// list, or an enum with one field list per variant.
+enum ItemArgs {
+    Struct(Vec<FieldWithArgs>),
+    Enum(Vec<VariantWithArgs>),
+}
+
+struct AbsDiffEqParser {
+    ident: syn::Ident,
+    generics: syn::Generics,
+    item: ItemArgs,
     struct_args: StructArgs,
 }
 
 impl syn::parse::Parse for AbsDiffEqParser {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let item_struct: syn::ItemStruct = input.parse()?;
-        let struct_args = StructArgs::from_attrs(&item_struct.attrs)?;
-        let fields_with_args = item_struct
-            .fields
-            .iter()
-            .map(|field| FieldWithArgs::from_field(field))
-            .collect::<syn::Result<Vec<_>>>()?;
+        let derive_input: syn::DeriveInput = input.parse()?;
+        let struct_args = StructArgs::from_attrs(&derive_input.attrs)?;
+        let item = match &derive_input.data {
+            syn::Data::Struct(data_struct) => {
+                let fields_with_args = data_struct
+                    .fields
+                    .iter()
+                    .map(FieldWithArgs::from_field)
+                    .collect::<syn::Result<Vec<_>>>()?;
+                ItemArgs::Struct(fields_with_args)
+            }
+            syn::Data::Enum(data_enum) => {
+                let variants = data_enum
+                    .variants
+                    .iter()
+                    .map(|variant| {
+                        let fields_with_args = variant
+                            .fields
+                            .iter()
+                            .map(FieldWithArgs::from_field)
+                            .collect::<syn::Result<Vec<_>>>()?;
+                        Ok(VariantWithArgs {
+                            ident: variant.ident.clone(),
+                            fields: variant.fields.clone(),
+                            fields_with_args,
+                        })
+                    })
+                    .collect::<syn::Result<Vec<_>>>()?;
+                ItemArgs::Enum(variants)
+            }
+            syn::Data::Union(_) => {
+                return Err(syn::Error::new_spanned(
+                    &derive_input,
+                    "approx_derive does not support unions",
+                ))
+            }
+        };
+        if struct_args.rhs_type.is_some() && matches!(item, ItemArgs::Enum(_)) {
+            return Err(syn::Error::new_spanned(
+                &derive_input,
+                "#[approx(rhs_type = ...)] is not supported on enums",
+            ));
+        }
         Ok(Self {
-            item_struct,
-            fields_with_args,
+            ident: derive_input.ident,
+            generics: derive_input.generics,
+            item,
             struct_args,
         })
     }
 }
 
+/// Builds the match-arm pattern for a single side (`self` or `other`) of an enum variant
+/// comparison, binding every one of its fields to a fresh, side-specific identifier.
+///
+/// Returns the pattern itself together with the bound identifiers in field order, so they can be
+/// paired back up with `fields_with_args` by the caller.
+fn variant_pattern(
+    variant_ident: &syn::Ident,
+    fields: &syn::Fields,
+    side: &str,
+) -> (proc_macro2::TokenStream, Vec<syn::Ident>) {
+    let bound_ident = |index: usize, field: &syn::Field| {
+        let base = field
+            .ident
+            .as_ref()
+            .map(syn::Ident::to_string)
+            .unwrap_or_else(|| format!("field_{index}"));
+        syn::Ident::new(&format!("__approx_{base}_{side}"), proc_macro2::Span::call_site())
+    };
+
+    match fields {
+        syn::Fields::Named(named) => {
+            let bound: Vec<_> = named
+                .named
+                .iter()
+                .enumerate()
+                .map(|(index, field)| bound_ident(index, field))
+                .collect();
+            let field_idents = named.named.iter().map(|field| field.ident.as_ref().unwrap());
+            (
+                quote::quote!(Self::#variant_ident { #(#field_idents: #bound),* }),
+                bound,
+            )
+        }
+        syn::Fields::Unnamed(unnamed) => {
+            let bound: Vec<_> = unnamed
+                .unnamed
+                .iter()
+                .enumerate()
+                .map(|(index, field)| bound_ident(index, field))
+                .collect();
+            (quote::quote!(Self::#variant_ident(#(#bound),*)), bound)
+        }
+        syn::Fields::Unit => (quote::quote!(Self::#variant_ident), Vec::new()),
+    }
+}
+
+/// Builds a field accessor for a struct field, using the field's name if it has one (a named
+/// struct) or its positional index otherwise (a tuple struct).
+fn struct_field_place(receiver: &str, field: &syn::Field, index: usize) -> proc_macro2::TokenStream {
+    let receiver = syn::Ident::new(receiver, proc_macro2::Span::call_site());
+    match &field.ident {
+        Some(ident) => quote::quote!(#receiver.#ident),
+        None => {
+            let index = syn::Index::from(index);
+            quote::quote!(#receiver.#index)
+        }
+    }
+}
+
+/// Builds the accessor for the `other` side of a field comparison. Normally this is just the
+/// same field on `other` as `struct_field_place` would give, but `#[approx(rhs_field = ...)]`
+/// lets a heterogeneous comparison (see `#[approx(rhs_type = ...)]`) pull the value from a
+/// differently-named field on the `Rhs` type instead.
+fn other_field_place(field_with_args: &FieldWithArgs, index: usize) -> proc_macro2::TokenStream {
+    match &field_with_args.args.rhs_field {
+        Some(rhs_field) => quote::quote!(other.#rhs_field),
+        None => struct_field_place("other", &field_with_args.field, index),
+    }
+}
+
 struct FieldFormatted {
     base_type: proc_macro2::TokenStream,
     own_field: proc_macro2::TokenStream,
     other_field: proc_macro2::TokenStream,
     epsilon: proc_macro2::TokenStream,
     max_relative: proc_macro2::TokenStream,
+    max_ulps: proc_macro2::TokenStream,
+    /// A user-supplied function that replaces the whole comparison, bypassing `base_type`
+    /// entirely. Set by `#[approx(compare_with = ...)]`.
+    compare_with: Option<syn::Path>,
 }
 
 impl AbsDiffEqParser {
+    /// `#[derive(UlpsEq)]` always compares against `Self` (see `implement_derive_ulps_eq`), unlike
+    /// `AbsDiffEq`/`RelativeEq` which honor `#[approx(rhs_type = ...)]`. Without this check, a
+    /// `rhs_type` combined with `UlpsEq` would fall through to `comparison_body`, which still
+    /// reads `rhs_field` overrides, producing nonsensical type errors instead of a clear rejection.
+    fn reject_rhs_type_for_ulps_eq(&self) -> syn::Result<()> {
+        if self.struct_args.rhs_type.is_some() {
+            return Err(syn::Error::new_spanned(
+                &self.ident,
+                "#[approx(rhs_type = ...)] is not supported by #[derive(UlpsEq)], since UlpsEq always compares against Self",
+            ));
+        }
+        Ok(())
+    }
+
+    /// The first field declared anywhere in the item that has no `map`/`compare_with` override:
+    /// the first such struct field, or for an enum the first such field of the first variant
+    /// that has one.
+    fn first_field(&self) -> Option<&FieldWithArgs> {
+        // Fields with a `map`/`compare_with` override are compared as whatever their mapping
+        // function returns, not as their own declared type, so they make a poor guess for the
+        // default epsilon type and are skipped here.
+        let is_inferrable = |field_with_args: &&FieldWithArgs| {
+            field_with_args.args.map.is_none() && field_with_args.args.compare_with.is_none()
+        };
+        match &self.item {
+            ItemArgs::Struct(fields_with_args) => {
+                fields_with_args.iter().find(is_inferrable)
+            }
+            ItemArgs::Enum(variants) => variants
+                .iter()
+                .find_map(|variant| variant.fields_with_args.iter().find(is_inferrable)),
+        }
+    }
+
     fn get_epsilon_type(&self) -> proc_macro2::TokenStream {
         self.struct_args
             .epsilon_type
             .clone()
             .and_then(|x| Some(quote::quote!(#x)))
             .or_else(|| {
-                self.fields_with_args.first().and_then(|field| {
+                self.first_field().and_then(|field| {
                     let eps_type = &field.field.ty;
                     Some(quote::quote!(#eps_type))
                 })
@@ -291,7 +622,24 @@ impl AbsDiffEqParser {
             .unwrap()
     }
 
-    fn format_field(&self, field_with_args: &FieldWithArgs) -> Option<FieldFormatted> {
+    fn get_max_ulps_default_value(&self) -> proc_macro2::TokenStream {
+        self.struct_args
+            .default_max_ulps_value
+            .clone()
+            .and_then(|x| Some(quote::quote!(#x)))
+            .or_else(|| Some(quote::quote!(4)))
+            .unwrap()
+    }
+
+    /// Formats a single field's comparison, given expressions for where to find its value on
+    /// each side (`self.field`/`other.field` for a struct, or the bound match variables for an
+    /// enum variant).
+    fn format_field(
+        &self,
+        field_with_args: &FieldWithArgs,
+        own_place: proc_macro2::TokenStream,
+        other_place: proc_macro2::TokenStream,
+    ) -> Option<FieldFormatted> {
         // Determine if this field will be skipped and exit early
         if field_with_args.args.skip {
             return None;
@@ -300,8 +648,7 @@ impl AbsDiffEqParser {
         // Get types for epsilon and max_relative
         let epsilon_type = self.get_epsilon_type();
 
-        // Save field name and type in variables for easy access
-        let field_name = &field_with_args.field.ident;
+        // Save field type in a variable for easy access
         let field_type = &field_with_args.field.ty;
 
         // Determine if the field or the value will be casted in any way
@@ -322,31 +669,73 @@ impl AbsDiffEqParser {
             .and_then(|x| Some(quote::quote!(#x)))
             .or_else(|| Some(quote::quote!(max_relative)))
             .unwrap();
+        let max_ulps = field_with_args
+            .args
+            .max_ulps_static_value
+            .clone()
+            .and_then(|x| Some(quote::quote!(#x)))
+            .or_else(|| Some(quote::quote!(max_ulps)))
+            .unwrap();
+
+        // `#[approx(compare_with = ...)]` bypasses the whole `base_type`/cast machinery: the
+        // comparison becomes a direct call to the user's function over the raw field places.
+        if let Some(compare_with) = &field_with_args.args.compare_with {
+            return Some(FieldFormatted {
+                base_type: quote::quote!(_),
+                own_field: quote::quote!(&(#own_place)),
+                other_field: quote::quote!(&(#other_place)),
+                epsilon: quote::quote!(#epsilon),
+                max_relative: quote::quote!(#max_relative),
+                max_ulps: quote::quote!(#max_ulps),
+                compare_with: Some(compare_with.clone()),
+            });
+        }
+
+        // `#[approx(map = ...)]` projects both sides through a user function before comparing
+        // them, so that fields whose own type does not implement the approx traits can still be
+        // compared (e.g. by reducing them to a float). This bypasses the cast strategy, since
+        // the type being compared is now whatever the mapping function returns rather than the
+        // field's own type.
+        if let Some(map_path) = &field_with_args.args.map {
+            return Some(FieldFormatted {
+                base_type: quote::quote!(_),
+                own_field: quote::quote!(&(#map_path(&(#own_place)))),
+                other_field: quote::quote!(&(#map_path(&(#other_place)))),
+                epsilon: quote::quote!(#epsilon),
+                max_relative: quote::quote!(#max_relative),
+                max_ulps: quote::quote!(#max_ulps),
+                compare_with: None,
+            });
+        }
 
         // Use the casting strategy
-        let (base_type, own_field, other_field, epsilon, max_relative) = match cast_strategy {
-            Some(TypeCast::CastField) => (
-                quote::quote!(#epsilon_type),
-                quote::quote!(&(self.#field_name as #epsilon_type)),
-                quote::quote!(&(other.#field_name as #epsilon_type)),
-                quote::quote!(#epsilon),
-                quote::quote!(#max_relative),
-            ),
-            Some(TypeCast::CastValue) => (
-                quote::quote!(#field_type),
-                quote::quote!(&self.#field_name),
-                quote::quote!(&other.#field_name),
-                quote::quote!(#epsilon as #field_type),
-                quote::quote!(#max_relative as #field_type),
-            ),
-            None => (
-                quote::quote!(#epsilon_type),
-                quote::quote!(&self.#field_name),
-                quote::quote!(&other.#field_name),
-                quote::quote!(#epsilon),
-                quote::quote!(#max_relative),
-            ),
-        };
+        let (base_type, own_field, other_field, epsilon, max_relative, max_ulps) =
+            match cast_strategy {
+                Some(TypeCast::CastField) => (
+                    quote::quote!(#epsilon_type),
+                    quote::quote!(&(#own_place as #epsilon_type)),
+                    quote::quote!(&(#other_place as #epsilon_type)),
+                    quote::quote!(#epsilon),
+                    quote::quote!(#max_relative),
+                    quote::quote!(#max_ulps),
+                ),
+                Some(TypeCast::CastValue) => (
+                    quote::quote!(#field_type),
+                    quote::quote!(&(#own_place)),
+                    quote::quote!(&(#other_place)),
+                    quote::quote!(#epsilon as #field_type),
+                    quote::quote!(#max_relative as #field_type),
+                    quote::quote!(#max_ulps),
+                ),
+                None => (
+                    quote::quote!(#epsilon_type),
+                    quote::quote!(&(#own_place)),
+                    quote::quote!(&(#other_place)),
+                    quote::quote!(#epsilon),
+                    quote::quote!(#max_relative),
+                    quote::quote!(#max_ulps),
+                ),
+            };
 
         // Return the fully formatted field
         Some(FieldFormatted {
@@ -355,111 +744,237 @@ impl AbsDiffEqParser {
             other_field,
             epsilon,
             max_relative,
+            max_ulps,
+            compare_with: None,
         })
     }
 
-    fn get_abs_diff_eq_fields(&self) -> Vec<proc_macro2::TokenStream> {
-        // We need to extend the where clause for all generics
-        let fields = self.fields_with_args.iter().filter_map(|field_with_args| {
-            if let Some(FieldFormatted {
+    /// Builds the match arm comparing one enum variant against itself, `&&`-folding the
+    /// per-field comparisons produced by `format_call`. Mismatched variants are left to the
+    /// caller's catch-all arm.
+    fn variant_arm(
+        &self,
+        variant: &VariantWithArgs,
+        format_call: &dyn Fn(&FieldFormatted) -> proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let (self_pattern, self_bound) = variant_pattern(&variant.ident, &variant.fields, "self");
+        let (other_pattern, other_bound) = variant_pattern(&variant.ident, &variant.fields, "other");
+
+        let comparisons = variant
+            .fields_with_args
+            .iter()
+            .zip(self_bound.into_iter().zip(other_bound))
+            .filter_map(|(field_with_args, (own_bound, other_bound))| {
+                self.format_field(
+                    field_with_args,
+                    quote::quote!(*#own_bound),
+                    quote::quote!(*#other_bound),
+                )
+                .as_ref()
+                .map(format_call)
+            });
+
+        quote::quote!(
+            (#self_pattern, #other_pattern) => { #(#comparisons)* true },
+        )
+    }
+
+    /// Builds the body of a comparison method (`abs_diff_eq`/`relative_eq`/`ulps_eq`), which is
+    /// either a flat `&&`-chain over struct fields, or a `match (self, other)` over enum variant
+    /// pairs, each arm of which is such a chain.
+    fn comparison_body(
+        &self,
+        format_call: impl Fn(&FieldFormatted) -> proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        match &self.item {
+            ItemArgs::Struct(fields_with_args) => {
+                let comparisons =
+                    fields_with_args
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(index, field_with_args)| {
+                            let own_place =
+                                struct_field_place("self", &field_with_args.field, index);
+                            let other_place = other_field_place(field_with_args, index);
+                            self.format_field(field_with_args, own_place, other_place)
+                                .as_ref()
+                                .map(&format_call)
+                        });
+                quote::quote!(#(#comparisons)* true)
+            }
+            ItemArgs::Enum(variants) => {
+                let arms = variants
+                    .iter()
+                    .map(|variant| self.variant_arm(variant, &format_call));
+                quote::quote!(
+                    match (self, other) {
+                        #(#arms)*
+                        _ => false,
+                    }
+                )
+            }
+        }
+    }
+
+    fn implement_derive_abs_diff_eq(&self) -> proc_macro2::TokenStream {
+        let type_name = &self.ident;
+        let (epsilon_type, epsilon_default_value) = self.get_epsilon_type_and_default_value();
+        let rhs_type = self.struct_args.rhs_type.clone();
+        let heterogeneous = rhs_type.is_some();
+        let body = self.comparison_body(|formatted| {
+            let FieldFormatted {
                 base_type,
                 own_field,
                 other_field,
                 epsilon,
-                #[allow(unused)]
-                max_relative,
-            }) = self.format_field(field_with_args)
-            {
-                Some(quote::quote!(
-                    <#base_type as approx::AbsDiffEq>::abs_diff_eq(
-                        #own_field,
-                        #other_field,
-                        #epsilon
-                    ) &&
-                ))
+                compare_with,
+                ..
+            } = formatted;
+            if let Some(compare_with) = compare_with {
+                quote::quote!( (#compare_with)(#own_field, #other_field, #epsilon) && )
             } else {
-                None
+                let trait_path = if heterogeneous {
+                    quote::quote!(approx::AbsDiffEq<_>)
+                } else {
+                    quote::quote!(approx::AbsDiffEq)
+                };
+                quote::quote!(
+                    <#base_type as #trait_path>::abs_diff_eq(#own_field, #other_field, #epsilon) &&
+                )
             }
         });
-        fields.collect()
+        let trait_rhs = rhs_type.as_ref().map(|rhs_type| quote::quote!(<#rhs_type>));
+        let other_type = rhs_type
+            .map(|rhs_type| quote::quote!(#rhs_type))
+            .unwrap_or_else(|| quote::quote!(Self));
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        quote::quote!(
+            const _ : () = {
+                #[automatically_derived]
+                impl #impl_generics approx::AbsDiffEq #trait_rhs for #type_name #ty_generics
+                #where_clause
+                {
+                    type Epsilon = #epsilon_type;
+
+                    fn default_epsilon() -> Self::Epsilon {
+                        #epsilon_default_value
+                    }
+
+                    fn abs_diff_eq(&self, other: &#other_type, epsilon: Self::Epsilon) -> bool {
+                        #body
+                    }
+                }
+            };
+        )
     }
 
-    fn get_rel_eq_fields(&self) -> Vec<proc_macro2::TokenStream> {
-        let fields = self.fields_with_args.iter().filter_map(|field_with_args| {
-            if let Some(FieldFormatted {
+    fn implement_derive_rel_diff_eq(&self) -> proc_macro2::TokenStream {
+        let type_name = &self.ident;
+        let max_relative_default_value = self.get_max_relative_default_value();
+        let rhs_type = self.struct_args.rhs_type.clone();
+        let heterogeneous = rhs_type.is_some();
+        let body = self.comparison_body(|formatted| {
+            let FieldFormatted {
                 base_type,
                 own_field,
                 other_field,
                 epsilon,
                 max_relative,
-            }) = self.format_field(field_with_args)
-            {
-                Some(quote::quote!(
-                    <#base_type as approx::RelativeEq>::relative_eq(
+                compare_with,
+                ..
+            } = formatted;
+            if let Some(compare_with) = compare_with {
+                // `compare_with` always has the 3-arg `fn(&T, &T, Epsilon) -> bool` signature,
+                // regardless of which trait is being derived; `max_relative` is unused here.
+                quote::quote!( (#compare_with)(#own_field, #other_field, #epsilon) && )
+            } else {
+                let trait_path = if heterogeneous {
+                    quote::quote!(approx::RelativeEq<_>)
+                } else {
+                    quote::quote!(approx::RelativeEq)
+                };
+                quote::quote!(
+                    <#base_type as #trait_path>::relative_eq(
                         #own_field,
                         #other_field,
                         #epsilon,
                         #max_relative
                     ) &&
-                ))
-            } else {
-                None
+                )
             }
         });
-        fields.collect()
-    }
-
-    fn implement_derive_abs_diff_eq(&self) -> proc_macro2::TokenStream {
-        let struct_name = &self.item_struct.ident;
-        let (epsilon_type, epsilon_default_value) = self.get_epsilon_type_and_default_value();
-        let fields = self.get_abs_diff_eq_fields();
-        let (impl_generics, ty_generics, where_clause) = self.item_struct.generics.split_for_impl();
+        let trait_rhs = rhs_type.as_ref().map(|rhs_type| quote::quote!(<#rhs_type>));
+        let other_type = rhs_type
+            .map(|rhs_type| quote::quote!(#rhs_type))
+            .unwrap_or_else(|| quote::quote!(Self));
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
 
         quote::quote!(
             const _ : () = {
                 #[automatically_derived]
-                impl #impl_generics approx::AbsDiffEq for #struct_name #ty_generics
+                impl #impl_generics approx::RelativeEq #trait_rhs for #type_name #ty_generics
                 #where_clause
                 {
-                    type Epsilon = #epsilon_type;
-
-                    fn default_epsilon() -> Self::Epsilon {
-                        #epsilon_default_value
+                    fn default_max_relative() -> Self::Epsilon {
+                        #max_relative_default_value
                     }
 
-                    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
-                        #(#fields)*
-                        true
+                    fn relative_eq(
+                        &self,
+                        other: &#other_type,
+                        epsilon: Self::Epsilon,
+                        max_relative: Self::Epsilon
+                    ) -> bool {
+                        #body
                     }
                 }
             };
         )
     }
 
-    fn implement_derive_rel_diff_eq(&self) -> proc_macro2::TokenStream {
-        let struct_name = &self.item_struct.ident;
-        let max_relative_default_value = self.get_max_relative_default_value();
-        let fields = self.get_rel_eq_fields();
-        let (impl_generics, ty_generics, where_clause) = self.item_struct.generics.split_for_impl();
+    fn implement_derive_ulps_eq(&self) -> proc_macro2::TokenStream {
+        let type_name = &self.ident;
+        let max_ulps_default_value = self.get_max_ulps_default_value();
+        let body = self.comparison_body(|formatted| {
+            let FieldFormatted {
+                base_type,
+                own_field,
+                other_field,
+                epsilon,
+                max_ulps,
+                compare_with,
+                ..
+            } = formatted;
+            if let Some(compare_with) = compare_with {
+                // `compare_with` always has the 3-arg `fn(&T, &T, Epsilon) -> bool` signature,
+                // regardless of which trait is being derived; `max_ulps` is unused here.
+                quote::quote!( (#compare_with)(#own_field, #other_field, #epsilon) && )
+            } else {
+                quote::quote!(
+                    <#base_type as approx::UlpsEq>::ulps_eq(#own_field, #other_field, #epsilon, #max_ulps) &&
+                )
+            }
+        });
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
 
         quote::quote!(
             const _ : () = {
                 #[automatically_derived]
-                impl #impl_generics approx::RelativeEq for #struct_name #ty_generics
+                impl #impl_generics approx::UlpsEq for #type_name #ty_generics
                 #where_clause
                 {
-                    fn default_max_relative() -> Self::Epsilon {
-                        #max_relative_default_value
+                    fn default_max_ulps() -> u32 {
+                        #max_ulps_default_value
                     }
 
-                    fn relative_eq(
+                    fn ulps_eq(
                         &self,
                         other: &Self,
                         epsilon: Self::Epsilon,
-                        max_relative: Self::Epsilon
+                        max_ulps: u32
                     ) -> bool {
-                        #(#fields)*
-                        true
+                        #body
                     }
                 }
             };
@@ -483,3 +998,20 @@ pub fn derive_rel_diff_eq(input: proc_macro::TokenStream) -> proc_macro::TokenSt
     output.extend(parsed.implement_derive_rel_diff_eq());
     output.into()
 }
+
+/// See the [crate] level documentation for a guide.
+///
+/// Deriving [UlpsEq] also derives [AbsDiffEq] and [RelativeEq], since `approx`'s [UlpsEq] trait
+/// requires both as supertraits.
+#[proc_macro_derive(UlpsEq, attributes(approx))]
+pub fn derive_ulps_eq(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let parsed = syn::parse_macro_input!(input as AbsDiffEqParser);
+    if let Err(err) = parsed.reject_rhs_type_for_ulps_eq() {
+        return err.into_compile_error().into();
+    }
+    let mut output = quote::quote!();
+    output.extend(parsed.implement_derive_abs_diff_eq());
+    output.extend(parsed.implement_derive_rel_diff_eq());
+    output.extend(parsed.implement_derive_ulps_eq());
+    output.into()
+}